@@ -6,153 +6,310 @@ use colored::Colorize;
 
 use crate::pawn::Pawn;
 
-const MAX_ROW: usize = 6;
-const MAX_COL: usize = 7;
-const MIN_CONNECT: usize = 4;
+mod ai;
+mod tui;
+
+pub use ai::Difficulty;
+
+const DEFAULT_ROW: usize = 6;
+const DEFAULT_COL: usize = 7;
+const DEFAULT_CONNECT: usize = 4;
+
+/// The four axes a connection can run along, expressed as `(row, col)` steps.
+/// Each axis is walked in both directions from the pivot cell.
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// Why a move could not be played.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MoveError {
+    /// The column index is outside `[0, cols)`.
+    OutOfBounds(usize),
+    /// The column has no empty cell left.
+    ColumnFull(usize),
+}
 
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::OutOfBounds(col) => write!(f, "column {col} is out of bounds"),
+            MoveError::ColumnFull(col) => write!(f, "column {col} is already full"),
+        }
+    }
+}
+
+/// How a finished game ended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    /// The given colour connected enough stones to win.
+    Win(Pawn),
+    /// The board filled up with no winner.
+    Draw,
+}
+
+/// How the two sides of the board are controlled.
+pub enum GameMode {
+    /// Two people taking turns at the same terminal.
+    HumanVsHuman,
+    /// A human playing `Pawn::Red` against the AI playing `Pawn::Blue`.
+    HumanVsAi(Difficulty),
+}
+
+/// A single action parsed from the column prompt.
+enum Command {
+    /// Drop a stone into the given column.
+    Column(usize),
+    /// Take back the last move.
+    Undo,
+    /// Replay the last undone move.
+    Redo,
+}
+
+#[derive(Clone)]
 pub struct ConnectFour {
-    /// Board matrix, stores the colored emojis.
-    board: [[Pawn; MAX_COL]; MAX_ROW],
+    rows: usize,
+    cols: usize,
+    /// Number of stones in a row needed to win.
+    connect: usize,
+    /// Flat board of `rows * cols` cells, indexed `row * cols + col` with row
+    /// `0` at the top.
+    board: Vec<Pawn>,
     turn: Pawn,
     is_connected: bool,
     is_draw: bool,
     moves_stack: Vec<(Pawn, (usize, usize))>,
+    redo_stack: Vec<(Pawn, (usize, usize))>,
 }
 
 /// Controller for `ConnectFour`, handles the concept / logic of the game.
 impl ConnectFour {
+    /// A standard 6×7 board where four in a row wins.
     fn new() -> Self {
+        Self::with_config(DEFAULT_ROW, DEFAULT_COL, DEFAULT_CONNECT)
+    }
+
+    /// A board with custom dimensions and win length, e.g. `8×8` Connect-Five.
+    fn with_config(rows: usize, cols: usize, connect: usize) -> Self {
         Self {
-            board: [[Pawn::White; MAX_COL]; MAX_ROW],
+            rows,
+            cols,
+            connect,
+            board: vec![Pawn::White; rows * cols],
             // Red starts first.
             turn: Pawn::Red,
             is_connected: false,
             is_draw: false,
             moves_stack: Vec::new(),
+            redo_stack: Vec::new(),
         }
     }
 
+    /// Flat index of the cell at `(row, col)`.
+    fn index(&self, row: usize, col: usize) -> usize {
+        row * self.cols + col
+    }
+
     /// Get empty row from the respective column.
     ///
     /// # Panics
-    /// When `col` is not in range `[0, MAX_COL)`
+    /// When `col` is not in range `[0, cols)`
     fn get_empty_spot(&self, col: usize) -> Option<usize> {
-        assert!(col < MAX_COL);
-        (0..MAX_ROW).rev().find(|&row| !self.is_set(row, col))
+        assert!(col < self.cols);
+        (0..self.rows).rev().find(|&row| !self.is_set(row, col))
     }
 
-    /// Check if the last placed pawn is connected to four other pawns of the same color.
-    /// Optimized to only check around the last placed pawn instead of the whole board.
+    /// Whether a stone can still be dropped into `col`.
+    fn can_play(&self, col: usize) -> bool {
+        self.get_empty_spot(col).is_some()
+    }
+
+    /// Length of the longest run of `pawn` starting one cell away from
+    /// `(row, col)` in direction `(dr, dc)` (the pivot itself is not counted).
+    fn run_len(&self, row: usize, col: usize, dr: isize, dc: isize, pawn: Pawn) -> usize {
+        let mut length = 0;
+        let mut r = row as isize + dr;
+        let mut c = col as isize + dc;
+        while r >= 0
+            && r < self.rows as isize
+            && c >= 0
+            && c < self.cols as isize
+            && self.board[self.index(r as usize, c as usize)] == pawn
+        {
+            length += 1;
+            r += dr;
+            c += dc;
+        }
+        length
+    }
+
+    /// Whether placing `pawn` at `(row, col)` would complete `connect` in a row
+    /// along any axis.
+    fn connects_at(&self, row: usize, col: usize, pawn: Pawn) -> bool {
+        DIRECTIONS.iter().any(|&(dr, dc)| {
+            1 + self.run_len(row, col, dr, dc, pawn) + self.run_len(row, col, -dr, -dc, pawn)
+                >= self.connect
+        })
+    }
+
+    /// Check if the pawn at `(row, col)` is connected to enough pawns of the
+    /// same color to win. Cheaper than scanning the whole board because it only
+    /// looks along the axes through that one cell.
     ///
     /// **Note:** Should be called after placing the pawn and before switching the pawn.
     ///
     /// # Panics
-    /// When `col` is not in range `[0, MAX_COL)`
-    /// When `row` is not in range `[0, MAX_ROW)`
+    /// When `col` is not in range `[0, cols)`
+    /// When `row` is not in range `[0, rows)`
     fn is_four_connected(&self, row: usize, col: usize) -> bool {
-        assert!(col < MAX_COL);
-        assert!(row < MAX_ROW);
-
-        // Checking if either of the axis from the pivot index (row, col) have any connections.
-        // Doing both at the same time, shouldn't be very expensive.
-        //
-        // Basically, just constructing two arrays: one where the indices appear in the horizontal
-        // axis and the other in which the indices appear in the vertical axis.
-        // Then we get a window of `MIN_CONNECT` elements and check if they all are equal to
-        // `self.turn`.
-        let axis_checks = [
-            // Horizontal check
-            self.board[row].try_into().unwrap(),
-            // Vertical check
-            self.board.iter().map(|r| r[col]).collect::<Vec<Pawn>>(),
-        ]
-        .iter()
-        .map(|r| {
-            r.windows(MIN_CONNECT)
-                .any(|window| window.iter().all(|&item| item == self.turn))
-        })
-        .any(|connected| connected);
+        assert!(col < self.cols);
+        assert!(row < self.rows);
 
-        if axis_checks {
-            return true;
-        }
+        let pawn = self.board[self.index(row, col)];
+        pawn != Pawn::White && self.connects_at(row, col, pawn)
+    }
 
-        [
-            // Diagonal (Top left to bottom right)
-            self.board
-                .iter()
-                .enumerate()
-                .flat_map(|(i, r)| {
-                    r.iter()
-                        .enumerate()
-                        .filter(move |(j, _)| {
-                            row as isize - i as isize == col as isize - *j as isize
-                        })
-                        .map(|(_, &p)| p)
-                })
-                .collect::<Vec<Pawn>>(),
-            self.board
-                .iter()
-                .enumerate()
-                .flat_map(|(i, r)| {
-                    r.iter()
-                        .enumerate()
-                        .filter(move |(j, _)| {
-                            row as isize - i as isize == *j as isize - col as isize
-                        })
-                        .map(|(_, &p)| p)
-                })
-                .collect::<Vec<Pawn>>(),
-        ]
-        .iter()
-        .map(|r| {
-            r.windows(MIN_CONNECT)
-                .any(|window| window.iter().all(|&item| item == self.turn))
-        })
-        .any(|connected| connected)
+    /// Whether dropping the current player's stone into `col` would win, without
+    /// mutating the board.
+    fn is_winning_move(&self, col: usize) -> bool {
+        match self.get_empty_spot(col) {
+            Some(row) => self.connects_at(row, col, self.turn),
+            None => false,
+        }
     }
 
     fn is_full(&self) -> bool {
-        self.board
-            .iter()
-            .all(|row| row.iter().all(|&item| item != Pawn::White))
+        self.board.iter().all(|&item| item != Pawn::White)
     }
 
-    fn is_over(&self) -> bool {
-        self.is_connected || self.is_draw
+    fn is_set(&self, row: usize, col: usize) -> bool {
+        self.board[self.index(row, col)] != Pawn::White
     }
 
-    fn is_set(&self, row: usize, col: usize) -> bool {
-        self.board[row][col] != Pawn::White
+    /// Number of stones currently on the board.
+    fn placed_count(&self) -> usize {
+        self.board.iter().filter(|&&item| item != Pawn::White).count()
     }
 
-    fn place(&mut self, row: usize, col: usize) {
+    /// Drop the current player's stone into `col` and refresh the flags, the
+    /// move history and turn. Shared core of [`place`](Self::place) and
+    /// [`redo`](Self::redo).
+    fn apply(&mut self, col: usize) {
+        let row = self.get_empty_spot(col).unwrap();
         self.moves_stack.push((self.turn, (row, col)));
-        self.board[row][col] = self.turn;
+        let index = self.index(row, col);
+        self.board[index] = self.turn;
         self.is_connected = self.is_four_connected(row, col);
         self.is_draw = self.is_full();
+        self.turn.switch();
+    }
+
+    /// Play a fresh move. A new move makes any previously undone moves
+    /// unreachable, so the redo history is cleared.
+    fn place(&mut self, col: usize) {
+        self.redo_stack.clear();
+        self.apply(col);
+    }
+
+    /// Columns that still have room for a stone.
+    pub fn available_moves(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.cols).filter(move |&col| self.can_play(col))
+    }
+
+    /// Colour of the player whose turn it is.
+    pub fn current_player(&self) -> Pawn {
+        self.turn
+    }
+
+    /// Drop a stone into `col` for the current player, validating the move
+    /// instead of panicking. This is the headless entry point used by bots and
+    /// front-ends alike.
+    pub fn play_move(&mut self, col: usize) -> Result<(), MoveError> {
+        if col >= self.cols {
+            return Err(MoveError::OutOfBounds(col));
+        }
+        if !self.can_play(col) {
+            return Err(MoveError::ColumnFull(col));
+        }
+        self.place(col);
+        Ok(())
+    }
+
+    /// The result of the game, or `None` while it is still in progress.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.is_connected {
+            self.moves_stack
+                .last()
+                .map(|&(pawn, _)| Outcome::Win(pawn))
+        } else if self.is_draw {
+            Some(Outcome::Draw)
+        } else {
+            None
+        }
     }
+
+    /// Take back the most recent move, clearing its cell, handing the turn back
+    /// to the player who made it and remembering the move so it can be redone.
+    pub fn undo(&mut self) {
+        let Some(mv @ (_, (row, col))) = self.moves_stack.pop() else {
+            return;
+        };
+
+        let index = self.index(row, col);
+        self.board[index] = Pawn::White;
+        self.turn.switch();
+
+        self.is_connected = match self.moves_stack.last() {
+            Some(&(_, (last_row, last_col))) => self.is_four_connected(last_row, last_col),
+            None => false,
+        };
+        self.is_draw = self.is_full();
+
+        self.redo_stack.push(mv);
+    }
+
+    /// Replay the most recently undone move.
+    pub fn redo(&mut self) {
+        if let Some((_, (_, col))) = self.redo_stack.pop() {
+            self.apply(col);
+        }
+    }
+
+    /// Lightweight move used by the search: drop a stone and hand the turn to
+    /// the opponent without touching the history or end-of-game flags. Returns
+    /// the row the stone landed in so it can be lifted again.
+    fn drop_stone(&mut self, col: usize) -> usize {
+        let row = self.get_empty_spot(col).unwrap();
+        let index = self.index(row, col);
+        self.board[index] = self.turn;
+        self.turn.switch();
+        row
+    }
+
+    /// Undo a [`drop_stone`](Self::drop_stone).
+    fn lift_stone(&mut self, row: usize, col: usize) {
+        let index = self.index(row, col);
+        self.board[index] = Pawn::White;
+        self.turn.switch();
+    }
+
 }
 
 /// View for `ConnectFour`, handles the io of the game.
 impl ConnectFour {
-    /// Helper function which prints the buffer and takes the column number as input.
-    /// Also converts the column number to `usize`
-    #[inline]
-    fn input_column_number(buffer: &str) -> usize {
+    /// Read a line from the column prompt and parse it into a [`Command`].
+    /// `u`/`r` undo and redo; anything else is treated as a column number and
+    /// yields `None` when it does not parse as one. The column is not checked
+    /// against the board here; [`play_move`](Self::play_move) rejects it later.
+    fn input_command(buffer: &str) -> Option<Command> {
         let mut input = String::new();
         print!("{buffer}");
         io::stdout().flush().unwrap();
         io::stdin().read_line(&mut input).unwrap();
-        input.trim().parse().unwrap()
-    }
 
-    #[inline]
-    fn validate_column_number(col: usize) -> Result<usize, &'static str> {
-        if col > MAX_COL {
-            return Err("Column number is out of bounds!");
+        match input.trim() {
+            "u" | "U" => Some(Command::Undo),
+            "r" | "R" => Some(Command::Redo),
+            other => other.parse().ok().map(Command::Column),
         }
-        Ok(col)
     }
 
     fn render_board(&self) {
@@ -160,30 +317,66 @@ impl ConnectFour {
         println!("{}", self);
     }
 
+    /// Ask the player which mode to start in before the first board is drawn.
+    fn input_game_mode() -> GameMode {
+        loop {
+            let mut input = String::new();
+            print!(
+                "Select mode:\n  0) Human vs Human\n  1) Human vs AI (easy)\n  \
+                 2) Human vs AI (medium)\n  3) Human vs AI (hard)\nMode: "
+            );
+            io::stdout().flush().unwrap();
+            io::stdin().read_line(&mut input).unwrap();
+
+            match input.trim().parse() {
+                Ok(0) => return GameMode::HumanVsHuman,
+                Ok(1) => return GameMode::HumanVsAi(Difficulty::Easy),
+                Ok(2) => return GameMode::HumanVsAi(Difficulty::Medium),
+                Ok(3) => return GameMode::HumanVsAi(Difficulty::Hard),
+                Ok::<usize, _>(_) | Err(_) => continue,
+            }
+        }
+    }
+
+    /// Play with the full-screen interactive front-end: arrow keys move a drop
+    /// cursor across the top of the board, Enter drops, `u` undoes and `q`
+    /// quits. Unlike [`run`](Self::run) this never panics on stray input.
+    pub fn run_tui() {
+        let mode = Self::input_game_mode();
+        tui::run(mode);
+    }
+
     pub fn run() {
+        let mode = Self::input_game_mode();
         let mut game = Self::new();
-        let mut col: usize;
 
-        while !game.is_over() {
+        while game.outcome().is_none() {
             // Clear the terminal and place the cursor at the beginning.
             game.render_board();
-            println!("{}'s turn", game.turn);
-
-            col = match Self::validate_column_number(Self::input_column_number(
-                "Enter column number: ",
-            )) {
-                Ok(col) => col,
-                Err(_) => continue,
-            };
-            game.place(game.get_empty_spot(col).unwrap(), col);
-            game.turn.switch();
+            println!("{}'s turn", game.current_player());
+
+            // Let the AI answer for the blue side in single-player mode.
+            if let GameMode::HumanVsAi(difficulty) = mode {
+                if game.current_player() == Pawn::Blue {
+                    let _ = game.play_move(ai::best_column(&game, difficulty));
+                    continue;
+                }
+            }
+
+            match Self::input_command("Enter column number (u: undo, r: redo): ") {
+                Some(Command::Column(col)) => {
+                    let _ = game.play_move(col);
+                }
+                Some(Command::Undo) => game.undo(),
+                Some(Command::Redo) => game.redo(),
+                None => {}
+            }
         }
 
         game.render_board();
-        if game.is_connected {
-            println!("{} won!", game.moves_stack.last().unwrap().0);
-        } else {
-            println!("Draw!");
+        match game.outcome() {
+            Some(Outcome::Win(pawn)) => println!("{} won!", pawn),
+            _ => println!("Draw!"),
         }
     }
 }
@@ -216,13 +409,11 @@ impl fmt::Display for ConnectFour {
             // Part from which the pawn will fall.
             "\n".to_string(),
             // The game board formatted with vertical bars surrounding it.
-            self.board
-                .iter()
+            (0..self.rows)
                 .map(|row| {
                     BoxTextures::VerticalBar.to_string()
-                        + &row
-                            .iter()
-                            .map(|pawn| pawn.to_string())
+                        + &(0..self.cols)
+                            .map(|col| self.board[self.index(row, col)].to_string())
                             .collect::<Vec<String>>()
                             .join("")
                         + BoxTextures::VerticalBar.to_string().as_str()
@@ -233,7 +424,7 @@ impl fmt::Display for ConnectFour {
             BoxTextures::BottomLeftCorner.to_string()
                 + BoxTextures::HorizontalBar
                     .to_string()
-                    .repeat(MAX_COL * 2)
+                    .repeat(self.cols * 2)
                     .as_str()
                 + BoxTextures::BottomRightCorner.to_string().as_str(),
         ]
@@ -245,17 +436,13 @@ impl fmt::Display for ConnectFour {
 
 #[cfg(test)]
 mod tests {
-    use super::ConnectFour;
     use super::Pawn::{self, *};
+    use super::{ConnectFour, MoveError, Outcome};
 
     fn from_board(board: [[Pawn; 7]; 6]) -> ConnectFour {
-        ConnectFour {
-            board,
-            is_draw: false,
-            is_connected: false,
-            turn: Red,
-            moves_stack: vec![],
-        }
+        let mut game = ConnectFour::new();
+        game.board = board.iter().flatten().copied().collect();
+        game
     }
 
     #[test]
@@ -361,4 +548,75 @@ mod tests {
         ]);
         assert!(!connect_four.is_four_connected(5, 0));
     }
+
+    #[test]
+    fn test_play_move_out_of_bounds() {
+        let mut game = ConnectFour::new();
+        assert_eq!(game.play_move(7), Err(MoveError::OutOfBounds(7)));
+    }
+
+    #[test]
+    fn test_play_move_column_full() {
+        let mut game = ConnectFour::new();
+        // Six rows, so the seventh drop into the same column is rejected.
+        for _ in 0..6 {
+            game.play_move(0).unwrap();
+        }
+        assert_eq!(game.play_move(0), Err(MoveError::ColumnFull(0)));
+    }
+
+    #[test]
+    fn test_available_moves_excludes_full_columns() {
+        let mut game = ConnectFour::new();
+        for _ in 0..6 {
+            game.play_move(0).unwrap();
+        }
+        let open: Vec<usize> = game.available_moves().collect();
+        assert_eq!(open, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_outcome_win() {
+        let mut game = ConnectFour::new();
+        // Red stacks column 0 while Blue answers in column 1.
+        for _ in 0..3 {
+            game.play_move(0).unwrap();
+            game.play_move(1).unwrap();
+        }
+        game.play_move(0).unwrap();
+        assert_eq!(game.outcome(), Some(Outcome::Win(Red)));
+    }
+
+    #[test]
+    fn test_outcome_draw() {
+        // A one-row, two-column board can never connect four, so filling it draws.
+        let mut game = ConnectFour::with_config(1, 2, 4);
+        game.play_move(0).unwrap();
+        game.play_move(1).unwrap();
+        assert_eq!(game.outcome(), Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn test_undo_round_trips_board_and_turn() {
+        let mut game = ConnectFour::new();
+        let turn = game.current_player();
+        game.play_move(3).unwrap();
+        game.undo();
+        assert!(game.board.iter().all(|&cell| cell == Pawn::White));
+        assert_eq!(game.current_player(), turn);
+    }
+
+    #[test]
+    fn test_place_clears_redo_stack() {
+        let mut game = ConnectFour::new();
+        game.play_move(3).unwrap();
+        game.undo();
+        assert_eq!(game.redo_stack.len(), 1);
+
+        // A fresh move makes the undone move unreachable.
+        game.play_move(4).unwrap();
+        assert!(game.redo_stack.is_empty());
+        game.redo();
+        assert_eq!(game.placed_count(), 1);
+    }
 }
@@ -0,0 +1,136 @@
+use super::ConnectFour;
+
+/// How hard the AI plays, expressed as the maximum search depth (in plies)
+/// the iterative deepening loop is allowed to reach.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Maximum number of plies the search is allowed to look ahead.
+    fn max_depth(self) -> usize {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Medium => 7,
+            Difficulty::Hard => 11,
+        }
+    }
+}
+
+/// Column visiting order from the centre outwards. Central columns take part
+/// in more winning lines, so exploring them first makes the alpha-beta window
+/// shrink faster and prunes more branches.
+fn column_order(cols: usize) -> Vec<usize> {
+    let center = cols / 2;
+    let mut order: Vec<usize> = (0..cols).collect();
+    order.sort_by_key(|&col| (col as isize - center as isize).abs());
+    order
+}
+
+/// Pick a column for the side that is about to move.
+///
+/// The search is a negamax with alpha-beta pruning wrapped in iterative
+/// deepening: it repeatedly searches one ply deeper until the difficulty's
+/// depth cap is reached, always keeping the best column found by the deepest
+/// completed iteration.
+pub fn best_column(game: &ConnectFour, difficulty: Difficulty) -> usize {
+    let mut search = game.clone();
+    let order = column_order(search.cols);
+    let total = search.rows * search.cols;
+    let played = search.placed_count();
+
+    // Fall back to the most central legal column so we always return
+    // something sensible even if every line looks equally (un)favourable.
+    let mut best = order
+        .iter()
+        .copied()
+        .find(|&col| search.can_play(col))
+        .unwrap_or(0);
+
+    for depth in 1..=difficulty.max_depth() {
+        let mut alpha = i32::MIN + 1;
+        let beta = i32::MAX - 1;
+        let mut best_at_depth = best;
+
+        for &col in &order {
+            if !search.can_play(col) {
+                continue;
+            }
+
+            let score = if search.is_winning_move(col) {
+                win_score(played + 1, total)
+            } else {
+                let row = search.drop_stone(col);
+                let score = -negamax(&mut search, &order, total, depth - 1, -beta, -alpha, played + 1);
+                search.lift_stone(row, col);
+                score
+            };
+
+            if score > alpha {
+                alpha = score;
+                best_at_depth = col;
+            }
+        }
+
+        best = best_at_depth;
+    }
+
+    best
+}
+
+/// Negamax with alpha-beta pruning over the shared board representation.
+///
+/// `played` is the number of stones already on the board at this node and is
+/// used to reward quicker wins. The window `[alpha, beta]` is negated on every
+/// recursive call, as usual for negamax.
+fn negamax(
+    game: &mut ConnectFour,
+    order: &[usize],
+    total: usize,
+    depth: usize,
+    mut alpha: i32,
+    beta: i32,
+    played: usize,
+) -> i32 {
+    if played >= total {
+        // Board is full and nobody has connected: a draw.
+        return 0;
+    }
+    if depth == 0 {
+        // Reached the difficulty's horizon; treat the position as balanced.
+        return 0;
+    }
+
+    for &col in order {
+        if !game.can_play(col) {
+            continue;
+        }
+
+        let score = if game.is_winning_move(col) {
+            win_score(played + 1, total)
+        } else {
+            let row = game.drop_stone(col);
+            let score = -negamax(game, order, total, depth - 1, -beta, -alpha, played + 1);
+            game.lift_stone(row, col);
+            score
+        };
+
+        if score >= beta {
+            return score;
+        }
+        if score > alpha {
+            alpha = score;
+        }
+    }
+
+    alpha
+}
+
+/// Score of a won position from the mover's perspective. Wins that need fewer
+/// stones to reach score higher, so the AI prefers the quickest forced win.
+fn win_score(played: usize, total: usize) -> i32 {
+    (total as i32 + 1 - played as i32) / 2
+}
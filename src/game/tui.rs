@@ -0,0 +1,121 @@
+use std::io::{self, Read};
+use std::process::Command;
+
+use colored::Colorize;
+
+use super::{ai, ConnectFour, GameMode, Outcome};
+use crate::pawn::Pawn;
+
+/// A key press understood by the interactive board.
+enum Key {
+    Left,
+    Right,
+    Drop,
+    Undo,
+    Quit,
+    Other,
+}
+
+/// Put the controlling terminal into raw mode so key presses arrive one at a
+/// time and are not echoed. Best-effort: if `stty` is missing the game still
+/// runs, it just falls back to line-buffered behaviour.
+fn enable_raw_mode() {
+    let _ = Command::new("stty").arg("raw").arg("-echo").status();
+}
+
+/// Restore the terminal to its normal cooked mode.
+fn disable_raw_mode() {
+    let _ = Command::new("stty").arg("-raw").arg("echo").status();
+}
+
+/// Read and classify a single key press, decoding the `ESC [ C` / `ESC [ D`
+/// escape sequences the arrow keys produce.
+fn read_key() -> io::Result<Key> {
+    let mut byte = [0u8; 1];
+    io::stdin().read_exact(&mut byte)?;
+
+    Ok(match byte[0] {
+        0x1b => {
+            let mut seq = [0u8; 2];
+            io::stdin().read_exact(&mut seq)?;
+            match (seq[0], seq[1]) {
+                (b'[', b'C') => Key::Right,
+                (b'[', b'D') => Key::Left,
+                _ => Key::Other,
+            }
+        }
+        b'\r' | b'\n' | b' ' => Key::Drop,
+        b'u' | b'U' => Key::Undo,
+        // `q` or Ctrl-C — the latter no longer raises a signal in raw mode.
+        b'q' | b'Q' | 0x03 => Key::Quit,
+        _ => Key::Other,
+    })
+}
+
+/// The line drawn above the open top of the board, previewing which colour
+/// will drop and into which column.
+fn cursor_line(game: &ConnectFour, cursor: usize) -> String {
+    // A leading space lines the markers up past the board's left border; each
+    // pawn glyph is two columns wide, matching one board cell.
+    let mut line = String::from(" ");
+    for col in 0..game.cols {
+        if col == cursor {
+            line += &game.current_player().to_string();
+        } else {
+            line += "  ";
+        }
+    }
+    line
+}
+
+/// Clear the screen and redraw the board with the drop cursor above `cursor`.
+fn render(game: &ConnectFour, cursor: usize) {
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println!("{}", cursor_line(game, cursor));
+    println!("{}", game);
+    println!(
+        "{}",
+        "← → move   ⏎ drop   u undo   q quit".dimmed()
+    );
+}
+
+/// Run the game with the full-screen interactive front-end.
+pub fn run(mode: GameMode) {
+    let mut game = ConnectFour::new();
+    let mut cursor = game.cols / 2;
+
+    enable_raw_mode();
+    while game.outcome().is_none() {
+        // Let the AI answer for the blue side in single-player mode.
+        if let GameMode::HumanVsAi(difficulty) = mode {
+            if game.current_player() == Pawn::Blue {
+                let _ = game.play_move(ai::best_column(&game, difficulty));
+                continue;
+            }
+        }
+
+        render(&game, cursor);
+        let key = match read_key() {
+            Ok(key) => key,
+            Err(_) => break,
+        };
+        match key {
+            Key::Left => cursor = cursor.saturating_sub(1),
+            Key::Right => cursor = (cursor + 1).min(game.cols - 1),
+            Key::Drop => {
+                let _ = game.play_move(cursor);
+            }
+            Key::Undo => game.undo(),
+            Key::Quit => break,
+            Key::Other => {}
+        }
+    }
+    disable_raw_mode();
+
+    render(&game, cursor);
+    match game.outcome() {
+        Some(Outcome::Win(pawn)) => println!("{} won!", pawn),
+        Some(Outcome::Draw) => println!("Draw!"),
+        None => println!("Bye!"),
+    }
+}